@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::code_type::CodeType;
+
+pub(super) struct CallbackInterfaceCodeType {
+    name: String,
+}
+
+impl CallbackInterfaceCodeType {
+    pub(super) fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl CodeType for CallbackInterfaceCodeType {
+    fn type_label(&self) -> String {
+        self.name.clone()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Long".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}Handles.register({})", self.name, nm)
+    }
+    fn lift(&self, _nm: &str) -> String {
+        panic!("callback interfaces are only ever passed Kotlin-to-Rust, never lifted back")
+    }
+    fn read(&self, _nm: &str) -> String {
+        panic!("callback interfaces are not embedded in a serialized buffer")
+    }
+}
@@ -0,0 +1,75 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::interface::*;
+
+use super::{callback_interface, compound, enums, object, primitives};
+
+// Everything the Kotlin backend needs to know about a single `TypeReference` in order to
+// move it across the FFI boundary: what it's called on each side, and how to convert
+// between the two. One impl per `TypeReference` variant, grouped by kind into submodules, so
+// that adding a new kind of type touches exactly one place (`find`, below) plus its own impl.
+pub(super) trait CodeType {
+    // The name of this type as it appears in the generated Kotlin public API.
+    fn type_label(&self) -> String;
+
+    // The name of the type used to pass/return this value across the raw JNA FFI boundary.
+    // Defaults to `type_label()`, which already holds for the types JNA marshals under their
+    // Kotlin name (the signed integers, `Float`, `Double`, `String`, and `Bytes`, which is
+    // exposed as a raw `RustBuffer.ByValue`).
+    fn ffi_type_label(&self) -> String {
+        self.type_label()
+    }
+
+    // Kotlin expression lowering a value named `nm: type_label()` into `ffi_type_label()`,
+    // ready to pass as a raw FFI call argument.
+    fn lower(&self, nm: &str) -> String;
+
+    // Kotlin expression lifting a value named `nm: ffi_type_label()`, just returned from a
+    // raw FFI call, back into `type_label()`.
+    fn lift(&self, nm: &str) -> String;
+
+    // Kotlin expression reading one value of this type out of the `ByteBuffer` named `nm`,
+    // e.g. when this type is a record field or an enum variant field.
+    fn read(&self, nm: &str) -> String;
+
+    // Any additional helper code this type needs emitted once per component. Every impl in
+    // this module keeps the `None` default, and the `KotlinWrapper` template does not call
+    // this method: the helpers in use live in the template's inline runtime.
+    fn helper_code(&self) -> Option<String> {
+        None
+    }
+}
+
+// Maps a `TypeReference` to the `CodeType` that knows how to talk about it in Kotlin. This
+// is the one place that needs to learn about a new `TypeReference` variant; everywhere else
+// in the generator goes through the trait above instead of matching on `TypeReference` itself,
+// apart from the `String` arm of `decl_c_return` in `mod.rs`.
+pub(super) fn find<'a>(type_: &'a TypeReference, ci: &'a ComponentInterface) -> Box<dyn CodeType + 'a> {
+    match type_ {
+        TypeReference::Boolean => Box::new(primitives::BooleanCodeType),
+        TypeReference::I8 => Box::new(primitives::I8CodeType),
+        TypeReference::U8 => Box::new(primitives::U8CodeType),
+        TypeReference::I16 => Box::new(primitives::I16CodeType),
+        TypeReference::U16 => Box::new(primitives::U16CodeType),
+        TypeReference::I32 => Box::new(primitives::I32CodeType),
+        TypeReference::U32 => Box::new(primitives::U32CodeType),
+        TypeReference::I64 => Box::new(primitives::I64CodeType),
+        TypeReference::U64 => Box::new(primitives::U64CodeType),
+        TypeReference::Float => Box::new(primitives::FloatCodeType),
+        TypeReference::Double => Box::new(primitives::DoubleCodeType),
+        TypeReference::String => Box::new(primitives::StringCodeType),
+        TypeReference::Bytes => Box::new(primitives::BytesCodeType),
+        TypeReference::Enum(name) => Box::new(enums::EnumCodeType::new(name.clone(), ci.is_enum_flat(name))),
+        TypeReference::Record(name) => Box::new(compound::RecordCodeType::new(name.clone())),
+        TypeReference::Optional(inner) => Box::new(compound::OptionalCodeType::new(find(inner, ci))),
+        TypeReference::Sequence(inner) => Box::new(compound::SequenceCodeType::new(find(inner, ci))),
+        TypeReference::Map(value) => Box::new(compound::MapCodeType::new(find(value, ci))),
+        TypeReference::Object(name) => Box::new(object::ObjectCodeType::new(name.clone())),
+        TypeReference::CallbackInterface(name) => {
+            Box::new(callback_interface::CallbackInterfaceCodeType::new(name.clone()))
+        }
+        _ => panic!("[TODO: CodeType for {:?}]", type_),
+    }
+}
@@ -0,0 +1,135 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::code_type::CodeType;
+
+pub(super) struct RecordCodeType {
+    name: String,
+}
+
+impl RecordCodeType {
+    pub(super) fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl CodeType for RecordCodeType {
+    fn type_label(&self) -> String {
+        self.name.clone()
+    }
+    fn ffi_type_label(&self) -> String {
+        "RustBuffer.ByValue".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.serializeForRust()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("deserializeFromRust({}) {{ buf -> {} }}", nm, self.read("buf"))
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("{}.deserializeItemFromRust({})", self.name, nm)
+    }
+}
+
+// Compound types recursively reference their inner type's `CodeType` for its label and its
+// `read`, so e.g. `List<MyRecord>` reads each element with `MyRecord`'s `read`. The count or
+// presence-flag framing around the elements is written inline here, and lowering goes through
+// the inline runtime's generic `serializeForRust()` extensions.
+pub(super) struct OptionalCodeType<'a> {
+    inner: Box<dyn CodeType + 'a>,
+}
+
+impl<'a> OptionalCodeType<'a> {
+    pub(super) fn new(inner: Box<dyn CodeType + 'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> CodeType for OptionalCodeType<'a> {
+    fn type_label(&self) -> String {
+        format!("{}?", self.inner.type_label())
+    }
+    fn ffi_type_label(&self) -> String {
+        "RustBuffer.ByValue".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.serializeForRust()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("deserializeFromRust({}) {{ buf -> {} }}", nm, self.read("buf"))
+    }
+    fn read(&self, nm: &str) -> String {
+        // There's no generic `T?.deserializeItemFromRust()` in Kotlin, so the presence flag
+        // is read inline and the inner value is read recursively only when it's present.
+        format!(
+            "(if (Boolean.deserializeItemFromRust({})) {{ {} }} else {{ null }})",
+            nm,
+            self.inner.read(nm)
+        )
+    }
+}
+
+pub(super) struct SequenceCodeType<'a> {
+    inner: Box<dyn CodeType + 'a>,
+}
+
+impl<'a> SequenceCodeType<'a> {
+    pub(super) fn new(inner: Box<dyn CodeType + 'a>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a> CodeType for SequenceCodeType<'a> {
+    fn type_label(&self) -> String {
+        format!("List<{}>", self.inner.type_label())
+    }
+    fn ffi_type_label(&self) -> String {
+        "RustBuffer.ByValue".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.serializeForRust()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("deserializeFromRust({}) {{ buf -> {} }}", nm, self.read("buf"))
+    }
+    fn read(&self, nm: &str) -> String {
+        format!(
+            "(0 until Int.deserializeItemFromRust({})).map {{ {} }}",
+            nm,
+            self.inner.read(nm)
+        )
+    }
+}
+
+pub(super) struct MapCodeType<'a> {
+    value: Box<dyn CodeType + 'a>,
+}
+
+impl<'a> MapCodeType<'a> {
+    pub(super) fn new(value: Box<dyn CodeType + 'a>) -> Self {
+        Self { value }
+    }
+}
+
+impl<'a> CodeType for MapCodeType<'a> {
+    fn type_label(&self) -> String {
+        format!("Map<String, {}>", self.value.type_label())
+    }
+    fn ffi_type_label(&self) -> String {
+        "RustBuffer.ByValue".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.serializeForRust()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("deserializeFromRust({}) {{ buf -> {} }}", nm, self.read("buf"))
+    }
+    fn read(&self, nm: &str) -> String {
+        format!(
+            "(0 until Int.deserializeItemFromRust({nm})).map {{ Pair(String.deserializeItemFromRust({nm}), {value}) }}.toMap()",
+            nm = nm,
+            value = self.value.read(nm)
+        )
+    }
+}
@@ -0,0 +1,52 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::code_type::CodeType;
+
+// A flat (C-style) enum is lowered to/from its ordinal `Int`; an enum with at least one
+// variant carrying fields is instead serialized into a `RustBuffer` like a record is.
+pub(super) struct EnumCodeType {
+    name: String,
+    is_flat: bool,
+}
+
+impl EnumCodeType {
+    pub(super) fn new(name: String, is_flat: bool) -> Self {
+        Self { name, is_flat }
+    }
+}
+
+impl CodeType for EnumCodeType {
+    fn type_label(&self) -> String {
+        self.name.clone()
+    }
+
+    fn ffi_type_label(&self) -> String {
+        if self.is_flat {
+            "Int".to_string()
+        } else {
+            "RustBuffer.ByValue".to_string()
+        }
+    }
+
+    fn lower(&self, nm: &str) -> String {
+        if self.is_flat {
+            format!("{}.ordinal", nm)
+        } else {
+            format!("{}.serializeForRust()", nm)
+        }
+    }
+
+    fn lift(&self, nm: &str) -> String {
+        if self.is_flat {
+            format!("{}.fromOrdinal({})", self.name, nm)
+        } else {
+            format!("deserializeFromRust({}) {{ buf -> {} }}", nm, self.read("buf"))
+        }
+    }
+
+    fn read(&self, nm: &str) -> String {
+        format!("{}.deserializeItemFromRust({})", self.name, nm)
+    }
+}
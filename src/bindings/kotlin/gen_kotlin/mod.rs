@@ -0,0 +1,826 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    env,
+    collections::HashMap,
+    convert::TryFrom, convert::TryInto,
+    fs::File,
+    iter::IntoIterator,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::bail;
+use anyhow::Result;
+use askama::Template;
+
+use crate::interface::*;
+
+mod callback_interface;
+mod code_type;
+mod compound;
+mod enums;
+mod object;
+mod primitives;
+
+// Some config options for it the caller wants to customize the generated Kotlin.
+// Note that this can only be used to control details of the Kotlin *that do not affect the underlying component*,
+// sine the details of the underlying component are entirely determined by the `ComponentInterface`.
+pub struct Config {
+    pub package_name: String
+}
+
+impl Config {
+    pub fn from(ci: &ComponentInterface) -> Self {
+        Config {
+            package_name: format!("uniffi.{}", ci.namespace())
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(ext="kt", escape="none", source=r#"
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+package {{ config.package_name }};
+
+// Common helper code.
+//
+// Ideally this would live in a separate .kt file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the detils of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the rust code on the other side of the interface. In practice right
+// now that means come from the exact some version of `uniffi` that was used to
+// compile the rust component. The easiest way to ensure this is to bundle the Kotlin
+// helpers directly inline.
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+
+inline fun <reified Lib : Library> loadIndirect(
+    componentName: String
+): Lib {
+    // XXX TODO: This will probably grow some magic for resolving megazording in future.
+    // E.g. we might start by looking for the named component in `libuniffi.so` and if
+    // that fails, fall back to loading it separately from `lib${componentName}.so`.
+    return Native.load<Lib>("uniffi_${componentName}", Lib::class.java)
+}
+
+@Structure.FieldOrder("len", "data")
+open class RustBuffer : Structure() {
+    @JvmField var len: Long = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference
+
+    @Suppress("TooGenericExceptionThrown")
+    fun asByteBuffer(): ByteBuffer? {
+        return this.data?.let {
+            val buf = it.getByteBuffer(0, this.len)
+            buf.order(ByteOrder.BIG_ENDIAN)
+            return buf
+        }
+    }
+}
+
+// Out-param struct used to carry success/error/panic status back from every FFI call.
+// `code` is 0 for success, 1 for a caught error (in which case `error` holds the
+// serialized error value) and 2 for a Rust panic (in which case `error` holds the
+// panic message as a UTF-8 string).
+@Structure.FieldOrder("code", "error")
+open class RustCallStatus : Structure() {
+    @JvmField var code: Int = 0
+    @JvmField var error: RustBuffer.ByValue = RustBuffer.ByValue()
+
+    class ByReference : RustCallStatus(), Structure.ByReference
+}
+
+// Classes implementing this interface know how to turn the error buffer from a failed
+// rust call (status code 1) into the exception type `E` they're associated with.
+internal interface CallStatusErrorHandler<E> {
+    fun lift(errorBuf: RustBuffer.ByValue): E
+}
+
+// Used for calls that don't declare any errors, where status code 1 should never happen.
+internal object NullCallStatusErrorHandler : CallStatusErrorHandler<InternalException> {
+    override fun lift(errorBuf: RustBuffer.ByValue): InternalException {
+        _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(errorBuf)
+        return InternalException("[Unexpected error from rust, this should never happen]")
+    }
+}
+
+// Thrown when rust panics, or when a function that declares no errors somehow receives one.
+class InternalException(message: String) : Exception(message)
+
+// Call a function on `_UniFFILib.INSTANCE`, passing it a `RustCallStatus` out-param, and
+// use `errorHandler` to turn a caught error (status code 1) into an exception of type `E`.
+// Status code 2 (a Rust panic) always becomes an `InternalException`.
+internal inline fun <U, E : Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus.ByReference) -> U): U {
+    val status = RustCallStatus.ByReference()
+    val returnValue = callback(status)
+    return when (status.code) {
+        0 -> returnValue
+        1 -> throw errorHandler.lift(status.error)
+        2 -> {
+            val message = deserializeFromRust(status.error) { buf ->
+                String(ByteArray(buf.remaining()).also { buf.get(it) }, Charsets.UTF_8)
+            }
+            throw InternalException(message)
+        }
+        else -> throw InternalException("Unknown rust call status code: ${status.code}")
+    }
+}
+
+// Convenience wrapper for `rustCallWithError` for functions that don't declare any errors.
+internal inline fun <U> rustCall(callback: (RustCallStatus.ByReference) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback)
+}
+
+public fun Boolean.Companion.deserializeItemFromRust(buf: ByteBuffer): Boolean {
+    return buf.get().toInt() != 0
+}
+
+public fun Byte.Companion.deserializeItemFromRust(buf: ByteBuffer): Byte {
+    return buf.get()
+}
+
+public fun Byte.serializeForRustSize(): Int {
+    return 1
+}
+
+public fun Byte.serializeForRustInto(buf: ByteBuffer) {
+    buf.put(this)
+}
+
+@ExperimentalUnsignedTypes
+public fun UByte.Companion.deserializeItemFromRust(buf: ByteBuffer): UByte {
+    return buf.get().toUByte()
+}
+
+@ExperimentalUnsignedTypes
+public fun UByte.serializeForRustSize(): Int {
+    return 1
+}
+
+@ExperimentalUnsignedTypes
+public fun UByte.serializeForRustInto(buf: ByteBuffer) {
+    buf.put(this.toByte())
+}
+
+public fun Short.Companion.deserializeItemFromRust(buf: ByteBuffer): Short {
+    return buf.getShort()
+}
+
+public fun Short.serializeForRustSize(): Int {
+    return 2
+}
+
+public fun Short.serializeForRustInto(buf: ByteBuffer) {
+    buf.putShort(this)
+}
+
+@ExperimentalUnsignedTypes
+public fun UShort.Companion.deserializeItemFromRust(buf: ByteBuffer): UShort {
+    return buf.getShort().toUShort()
+}
+
+@ExperimentalUnsignedTypes
+public fun UShort.serializeForRustSize(): Int {
+    return 2
+}
+
+@ExperimentalUnsignedTypes
+public fun UShort.serializeForRustInto(buf: ByteBuffer) {
+    buf.putShort(this.toShort())
+}
+
+@ExperimentalUnsignedTypes
+public fun UInt.Companion.deserializeItemFromRust(buf: ByteBuffer): UInt {
+    return buf.getInt().toUInt()
+}
+
+@ExperimentalUnsignedTypes
+public fun UInt.serializeForRustSize(): Int {
+    return 4
+}
+
+@ExperimentalUnsignedTypes
+public fun UInt.serializeForRustInto(buf: ByteBuffer) {
+    buf.putInt(this.toInt())
+}
+
+public fun Long.Companion.deserializeItemFromRust(buf: ByteBuffer): Long {
+    return buf.getLong()
+}
+
+public fun Long.serializeForRustSize(): Int {
+    return 8
+}
+
+public fun Long.serializeForRustInto(buf: ByteBuffer) {
+    buf.putLong(this)
+}
+
+@ExperimentalUnsignedTypes
+public fun ULong.Companion.deserializeItemFromRust(buf: ByteBuffer): ULong {
+    return buf.getLong().toULong()
+}
+
+@ExperimentalUnsignedTypes
+public fun ULong.serializeForRustSize(): Int {
+    return 8
+}
+
+@ExperimentalUnsignedTypes
+public fun ULong.serializeForRustInto(buf: ByteBuffer) {
+    buf.putLong(this.toLong())
+}
+
+public fun String.Companion.deserializeItemFromRust(buf: ByteBuffer): String {
+    val len = buf.getInt()
+    val bytes = ByteArray(len)
+    buf.get(bytes)
+    return bytes.toString(Charsets.UTF_8)
+}
+
+public fun String.serializeForRustSize(): Int {
+    return 4 + this.toByteArray(Charsets.UTF_8).size
+}
+
+public fun String.serializeForRustInto(buf: ByteBuffer) {
+    val bytes = this.toByteArray(Charsets.UTF_8)
+    buf.putInt(bytes.size)
+    buf.put(bytes)
+}
+
+public fun<T> List<T>.serializeForRustSize(): Int {
+    var len = 4
+    for (item in this) {
+        len += item.serializeForRustSize()
+    }
+    return len
+}
+
+public fun<T> List<T>.serializeForRustInto(buf: ByteBuffer) {
+    buf.putInt(this.size)
+    for (item in this) {
+        item.serializeForRustInto(buf)
+    }
+}
+
+public fun<V> Map<String, V>.serializeForRustSize(): Int {
+    var len = 4
+    for ((k, v) in this) {
+        len += k.serializeForRustSize()
+        len += v.serializeForRustSize()
+    }
+    return len
+}
+
+public fun<V> Map<String, V>.serializeForRustInto(buf: ByteBuffer) {
+    buf.putInt(this.size)
+    for ((k, v) in this) {
+        k.serializeForRustInto(buf)
+        v.serializeForRustInto(buf)
+    }
+}
+
+// `List`/`Map`-typed overloads of `serializeForRust()`, so that a lowered sequence or map
+// resolves to the `Int`-count wire format above rather than the generic `Any?.serializeForRust()`
+// below (which would otherwise win and encode it as a nullable presence flag instead).
+internal fun<T> List<T>.serializeForRust(): RustBuffer.ByValue {
+    val buf = _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_alloc().name() }}(this.serializeForRustSize())
+    try {
+        this.serializeForRustInto(buf.asByteBuffer()!!)
+        return buf
+    } catch (e: Throwable) {
+        _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(buf)
+        throw e;
+    }
+}
+
+internal fun<V> Map<String, V>.serializeForRust(): RustBuffer.ByValue {
+    val buf = _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_alloc().name() }}(this.serializeForRustSize())
+    try {
+        this.serializeForRustInto(buf.asByteBuffer()!!)
+        return buf
+    } catch (e: Throwable) {
+        _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(buf)
+        throw e;
+    }
+}
+
+public fun Int.Companion.deserializeItemFromRust(buf: ByteBuffer): Int {
+    return buf.getInt()
+}
+
+public fun Int.serializeForRustSize(): Int {
+    return 4
+}
+
+public fun Int.serializeForRustInto(buf: ByteBuffer) {
+    buf.putInt(this)
+}
+
+public fun Float.Companion.deserializeItemFromRust(buf: ByteBuffer): Float {
+    return buf.getFloat()
+}
+
+public fun Float.serializeForRustSize(): Int {
+    return 4
+}
+
+public fun Float.serializeForRustInto(buf: ByteBuffer) {
+    buf.putFloat(this)
+}
+
+public fun Double.Companion.deserializeItemFromRust(buf: ByteBuffer): Double {
+    return buf.getDouble()
+}
+
+public fun Double.serializeForRustSize(): Int {
+    return 8
+}
+
+public fun Double.serializeForRustInto(buf: ByteBuffer) {
+    buf.putDouble(this)
+}
+
+public fun<T> T?.serializeForRustSize(): Int {
+    if (this === null) return 1
+    return 1 + this.serializeForRustSize()
+}
+
+public fun<T> T?.serializeForRustInto(buf: ByteBuffer) {
+    if (this === null) buf.put(0)
+    else {
+        buf.put(1)
+        this.serializeForRustInto(buf)
+    }
+}
+
+internal fun Any?.serializeForRust(): RustBuffer.ByValue {
+    val buf = _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_alloc().name() }}(this.serializeForRustSize())
+    try {
+        this.serializeForRustInto(buf.asByteBuffer()!!)
+        return buf
+    } catch (e: Throwable) {
+        _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(buf)
+        throw e;
+    }
+}
+
+public fun<T> deserializeFromRust(rbuf: RustBuffer.ByValue, deserializeItemFromRust: (ByteBuffer) -> T): T {
+    val buf = rbuf.asByteBuffer()!!
+    try {
+       val item = deserializeItemFromRust(buf)
+       if (buf.hasRemaining()) {
+           throw RuntimeException("junk remaining in record buffer, something is very wrong!!")
+       }
+       return item
+    } finally {
+        _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(rbuf)
+    }
+}
+
+// A JNA Library to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+internal interface _UniFFILib : Library {
+    companion object {
+        internal var INSTANCE: _UniFFILib = loadIndirect(componentName = "{{ ci.namespace() }}")
+    }
+
+    {% for func in ci.iter_ffi_function_definitions() -%}
+        fun {{ func.name() }}(
+        {%- for arg in func.arguments() %}
+            {{ arg.name() }}: {{ arg.type_()|decl_c_argument(ci) }},
+        {%- endfor %}
+        {%- if func.has_rust_call_status() %}
+            uniffiCallStatus: RustCallStatus.ByReference
+        {%- endif %}
+        ) {%- match func.return_type() -%}
+        {%- when Some with (type_) %}
+            : {{ type_|decl_c_return(ci) }}
+        {% when None -%}
+        {%- endmatch %}
+    {% endfor -%}
+}
+
+// Public interface members begin here.
+
+{% for e in ci.iter_enum_definitions() %}
+{% if ci.is_enum_flat(e.name()) %}
+    enum class {{ e.name() }} {
+        {% for value in e.values() %}
+        {{ value }}{% if loop.last %};{% else %},{% endif %}
+        {% endfor %}
+
+        companion object {
+            internal fun fromOrdinal(n: Int): {{ e.name() }} {
+                return when (n) {
+                  {% for value in e.values() %}
+                  {{ loop.index }} -> {{ value }}
+                  {% endfor %}
+                  else -> {
+                      throw RuntimeException("invalid enum value, something is very wrong!!")
+                  }
+                }
+            }
+        }
+    }
+{% else %}
+    // A variant carrying fields is a `data class`; a field-less variant is an `object`, since a
+    // `data class` requires at least one primary-constructor parameter. The wire format is an
+    // `Int` discriminant (1-based, matching the order below) followed by the variant's fields
+    // in order (none, for an `object` variant).
+    sealed class {{ e.name() }} {
+        {% for variant in e.variants() %}
+        {% if variant.fields().is_empty() %}
+        object {{ variant.name() }} : {{ e.name() }}()
+        {% else %}
+        data class {{ variant.name() }}(
+            {%- for field in variant.fields() %}
+            val {{ field.name() }}: {{ field.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        ) : {{ e.name() }}()
+        {% endif %}
+        {% endfor %}
+
+        companion object {
+            internal fun deserializeItemFromRust(buf: ByteBuffer): {{ e.name() }} {
+                return when (Int.deserializeItemFromRust(buf)) {
+                    {% for variant in e.variants() %}
+                    {% if variant.fields().is_empty() %}
+                    {{ loop.index }} -> {{ e.name() }}.{{ variant.name() }}
+                    {% else %}
+                    {{ loop.index }} -> {{ e.name() }}.{{ variant.name() }}(
+                        {%- for field in variant.fields() %}
+                        {{ "buf"|deserialize_item_kt(field.type_(), ci) }}{% if loop.last %}{% else %},{% endif %}
+                        {%- endfor %}
+                    )
+                    {% endif %}
+                    {% endfor %}
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
+            }
+        }
+
+        internal fun serializeForRust(): RustBuffer.ByValue {
+            val buf = _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_alloc().name() }}(this.serializeForRustSize())
+            try {
+                this.serializeForRustInto(buf.asByteBuffer()!!)
+                return buf
+            } catch (e: Throwable) {
+                _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(buf)
+                throw e;
+            }
+        }
+
+        internal fun serializeForRustSize(): Int {
+            return 4 + when (this) {
+                {% for variant in e.variants() %}
+                is {{ e.name() }}.{{ variant.name() }} -> 0{% for field in variant.fields() %} + this.{{ field.name() }}.serializeForRustSize(){% endfor %}
+                {% endfor %}
+            }
+        }
+
+        internal fun serializeForRustInto(buf: ByteBuffer) {
+            when (this) {
+                {% for variant in e.variants() %}
+                is {{ e.name() }}.{{ variant.name() }} -> {
+                    {{ loop.index }}.serializeForRustInto(buf)
+                    {%- for field in variant.fields() %}
+                    this.{{ field.name() }}.serializeForRustInto(buf)
+                    {%- endfor %}
+                }
+                {% endfor %}
+            }
+        }
+    }
+{% endif %}
+{%- endfor -%}
+
+{% for e in ci.iter_error_definitions() %}
+    sealed class {{ e.name() }} : Exception() {
+        {% for value in e.values() %}
+        class {{ value }} : {{ e.name() }}()
+        {% endfor %}
+
+        companion object ErrorHandler : CallStatusErrorHandler<{{ e.name() }}> {
+            override fun lift(errorBuf: RustBuffer.ByValue): {{ e.name() }} {
+                return deserializeFromRust(errorBuf) { buf -> deserializeItemFromRust(buf) }
+            }
+
+            internal fun deserializeItemFromRust(buf: ByteBuffer): {{ e.name() }} {
+                return when (Int.deserializeItemFromRust(buf)) {
+                    {% for value in e.values() %}
+                    {{ loop.index }} -> {{ value }}()
+                    {% endfor %}
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+{%- endfor -%}
+
+{%- for rec in ci.iter_record_definitions() %}
+    data class {{ rec.name() }} (
+      {%- for field in rec.fields() %}
+        val {{ field.name() }}: {{ field.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+      {%- endfor %}
+    ) {
+      companion object {
+          internal fun deserializeItemFromRust(buf: ByteBuffer): {{ rec.name() }} {
+              return {{ rec.name() }}(
+                {%- for field in rec.fields() %}
+                {{ "buf"|deserialize_item_kt(field.type_(), ci) }}{% if loop.last %}{% else %},{% endif %}
+                {%- endfor %}
+              )
+          }
+      }
+
+      internal fun serializeForRust(): RustBuffer.ByValue {
+          val buf = _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_alloc().name() }}(this.serializeForRustSize())
+          try {
+                this.serializeForRustInto(buf.asByteBuffer()!!)
+                return buf
+          } catch (e: Throwable) {
+                _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(buf)
+                throw e;
+          }
+      }
+
+      internal fun serializeForRustSize(): Int {
+          return 0 +
+            {%- for field in rec.fields() %}
+            this.{{ field.name() }}.serializeForRustSize(){% if loop.last %}{% else %} +{% endif %}
+            {%- endfor %}
+      }
+
+      internal fun serializeForRustInto(buf: ByteBuffer) {
+          {%- for field in rec.fields() %}
+          this.{{ field.name() }}.serializeForRustInto(buf)
+          {%- endfor %}
+      }
+    }
+
+{% endfor %}
+
+{% for func in ci.iter_function_definitions() %}
+
+    {%- match func.return_type() -%}
+    {%- when Some with (return_type) %}
+
+        fun {{ func.name() }}(
+            {%- for arg in func.arguments() %}
+                {{ arg.name() }}: {{ arg.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        ): {{ return_type|decl_kt(ci) }} {
+            val _retval = {% match func.throws_name() %}{% when Some with (error_name) %}rustCallWithError({{ error_name }}){% when None %}rustCall{% endmatch %} { uniffiCallStatus ->
+                _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
+                    {%- for arg in func.arguments() %}
+                        {{ arg.name()|lower_kt(arg.type_(), ci) }},
+                        {%- endfor %}
+                    uniffiCallStatus
+                )
+            }
+            return {{ "_retval"|lift_kt(return_type, ci) }}
+        }
+
+    {% when None -%}
+
+        fun {{ func.name() }}(
+            {%- for arg in func.arguments() %}
+                {{ arg.name() }}: {{ arg.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        ) {
+            {% match func.throws_name() %}{% when Some with (error_name) %}rustCallWithError({{ error_name }}){% when None %}rustCall{% endmatch %} { uniffiCallStatus ->
+                _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
+                    {%- for arg in func.arguments() %}
+                        {{ arg.name()|lower_kt(arg.type_(), ci) }},
+                        {%- endfor %}
+                    uniffiCallStatus
+                )
+            }
+        }
+
+    {%- endmatch %}
+{% endfor %}
+
+{% for obj in ci.iter_object_definitions() %}
+    class {{ obj.name() }} internal constructor(internal val pointer: Pointer) : AutoCloseable {
+        constructor() : this(
+            rustCall { uniffiCallStatus ->
+                _UniFFILib.INSTANCE.{{ obj.ffi_object_new().name() }}(uniffiCallStatus)
+            }
+        )
+
+        private val wasDestroyed = java.util.concurrent.atomic.AtomicBoolean(false)
+
+        override fun close() {
+            this.destroy()
+        }
+
+        // Guards against the object's FFI free function being called more than once, which
+        // would be a double-free of the Rust-side handle.
+        fun destroy() {
+            if (this.wasDestroyed.compareAndSet(false, true)) {
+                rustCall { uniffiCallStatus ->
+                    _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(this.pointer, uniffiCallStatus)
+                }
+            }
+        }
+
+        {% for meth in obj.methods() %}
+        {%- match meth.return_type() -%}
+        {%- when Some with (return_type) %}
+
+            fun {{ meth.name() }}(
+                {%- for arg in meth.arguments() %}
+                    {{ arg.name() }}: {{ arg.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+                {%- endfor %}
+            ): {{ return_type|decl_kt(ci) }} {
+                val _retval = {% match meth.throws_name() %}{% when Some with (error_name) %}rustCallWithError({{ error_name }}){% when None %}rustCall{% endmatch %} { uniffiCallStatus ->
+                    _UniFFILib.INSTANCE.{{ meth.ffi_func().name() }}(
+                        this.pointer,
+                        {%- for arg in meth.arguments() %}
+                            {{ arg.name()|lower_kt(arg.type_(), ci) }},
+                        {%- endfor %}
+                        uniffiCallStatus
+                    )
+                }
+                return {{ "_retval"|lift_kt(return_type, ci) }}
+            }
+
+        {% when None -%}
+
+            fun {{ meth.name() }}(
+                {%- for arg in meth.arguments() %}
+                    {{ arg.name() }}: {{ arg.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+                {%- endfor %}
+            ) {
+                {% match meth.throws_name() %}{% when Some with (error_name) %}rustCallWithError({{ error_name }}){% when None %}rustCall{% endmatch %} { uniffiCallStatus ->
+                    _UniFFILib.INSTANCE.{{ meth.ffi_func().name() }}(
+                        this.pointer,
+                        {%- for arg in meth.arguments() %}
+                            {{ arg.name()|lower_kt(arg.type_(), ci) }},
+                        {%- endfor %}
+                        uniffiCallStatus
+                    )
+                }
+            }
+
+        {%- endmatch %}
+        {% endfor %}
+    }
+{% endfor %}
+
+{% for cbi in ci.iter_callback_interface_definitions() %}
+    // Implemented in Kotlin and invoked from Rust, the inverse of the plain functions above.
+    interface {{ cbi.name() }} {
+        {% for meth in cbi.methods() %}
+        fun {{ meth.name() }}(
+            {%- for arg in meth.arguments() %}
+            {{ arg.name() }}: {{ arg.type_()|decl_kt(ci) }}{% if loop.last %}{% else %},{% endif %}
+            {%- endfor %}
+        ){%- match meth.return_type() -%}{%- when Some with (return_type) %}: {{ return_type|decl_kt(ci) }}{%- when None -%}{%- endmatch %}
+        {% endfor %}
+    }
+
+    // Live Kotlin implementations of `{{ cbi.name() }}` are tracked by an opaque handle, so that
+    // a call arriving from Rust can be routed back to the instance that registered it.
+    internal object {{ cbi.name() }}Handles {
+        private val map = java.util.concurrent.ConcurrentHashMap<Long, {{ cbi.name() }}>()
+        private val counter = java.util.concurrent.atomic.AtomicLong(1)
+
+        internal fun register(callback: {{ cbi.name() }}): Long {
+            val handle = counter.getAndIncrement()
+            map[handle] = callback
+            return handle
+        }
+
+        internal fun get(handle: Long): {{ cbi.name() }}? = map[handle]
+
+        internal fun drop(handle: Long) {
+            map.remove(handle)
+        }
+    }
+
+    // The JNA function pointer Rust calls into. `method` selects which `{{ cbi.name() }}` method
+    // to dispatch to (method 0 is reserved: it tells us Rust has dropped its handle, so we can
+    // release the corresponding Kotlin instance), `argsData` holds the serialized arguments, and
+    // `outBuf` receives the serialized return value. Returns 0 on success, 1 if the Kotlin
+    // implementation threw, and 2 if `handle` or `method` wasn't recognized.
+    internal object {{ cbi.name() }}ForeignCallback : com.sun.jna.Callback {
+        @Suppress("TooGenericExceptionCaught", "UNUSED_PARAMETER")
+        fun callback(handle: Long, method: Int, argsData: RustBuffer.ByValue, outBuf: RustBuffer.ByReference): Int {
+            return try {
+                if (method == 0) {
+                    // Rust has dropped its handle; release the Kotlin instance it was keeping alive.
+                    {{ cbi.name() }}Handles.drop(handle)
+                    return 0
+                }
+                val cb = {{ cbi.name() }}Handles.get(handle) ?: return 2
+                // `data` is null for a zero-argument method call; fall back to an empty buffer
+                // rather than forcing it, since there's nothing for those methods to read anyway.
+                val buf = argsData.asByteBuffer() ?: ByteBuffer.allocate(0)
+                when (method) {
+                    {% for meth in cbi.methods() %}
+                    {{ loop.index }} -> {
+                        {% for arg in meth.arguments() %}
+                        val {{ arg.name() }} = {{ "buf"|deserialize_item_kt(arg.type_(), ci) }}
+                        {% endfor %}
+                        {%- match meth.return_type() %}
+                        {%- when Some with (_) %}
+                        val _retval = cb.{{ meth.name() }}(
+                            {%- for arg in meth.arguments() %}
+                            {{ arg.name() }}{% if loop.last %}{% else %},{% endif %}
+                            {%- endfor %}
+                        )
+                        val rbuf = _retval.serializeForRust()
+                        outBuf.len = rbuf.len
+                        outBuf.data = rbuf.data
+                        outBuf.write()
+                        {%- when None %}
+                        cb.{{ meth.name() }}(
+                            {%- for arg in meth.arguments() %}
+                            {{ arg.name() }}{% if loop.last %}{% else %},{% endif %}
+                            {%- endfor %}
+                        )
+                        {%- endmatch %}
+                        0
+                    }
+                    {% endfor %}
+                    else -> 2
+                }
+            } catch (e: Exception) {
+                1
+            } finally {
+                _UniFFILib.INSTANCE.{{ ci.ffi_bytebuffer_free().name() }}(argsData)
+            }
+        }
+    }
+
+    // Registers the callback above with the Rust side the first time this file is loaded.
+    private val register{{ cbi.name() }}Callback: Boolean = run {
+        _UniFFILib.INSTANCE.{{ cbi.ffi_init_callback().name() }}({{ cbi.name() }}ForeignCallback)
+        true
+    }
+{% endfor %}
+"#)]
+pub struct KotlinWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+impl<'a> KotlinWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+}
+
+// Thin askama-facing wrappers around the `CodeType` oracle (see `code_type.rs`). Apart from
+// the `String` arm of `decl_c_return`, none of these carries type-specific logic of its own;
+// that belongs in a `CodeType` impl.
+mod filters {
+    use std::fmt;
+    use super::*;
+    use super::code_type::find;
+
+    pub fn decl_c_argument(type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(find(type_, ci).ffi_type_label())
+    }
+
+    pub fn decl_c_return(type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            TypeReference::String => "String".to_string(), // XXX TODO: I think maybe needs to be a ByteBuffer in return position..?
+            _ => decl_c_argument(type_, ci)?
+        })
+    }
+
+    pub fn decl_kt(type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(find(type_, ci).type_label())
+    }
+
+    pub fn lower_kt(nm: &dyn fmt::Display, type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(find(type_, ci).lower(&nm.to_string()))
+    }
+
+    pub fn lift_kt(nm: &dyn fmt::Display, type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(find(type_, ci).lift(&nm.to_string()))
+    }
+
+    pub fn deserialize_item_kt(nm: &dyn fmt::Display, type_: &TypeReference, ci: &ComponentInterface) -> Result<String, askama::Error> {
+        Ok(find(type_, ci).read(&nm.to_string()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::code_type::CodeType;
+
+pub(super) struct ObjectCodeType {
+    name: String,
+}
+
+impl ObjectCodeType {
+    pub(super) fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+impl CodeType for ObjectCodeType {
+    fn type_label(&self) -> String {
+        self.name.clone()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Pointer".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.pointer", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("{}({})", self.name, nm)
+    }
+    fn read(&self, _nm: &str) -> String {
+        panic!("objects are passed by pointer across the FFI, never embedded in a serialized buffer")
+    }
+}
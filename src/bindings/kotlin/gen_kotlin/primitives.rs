@@ -0,0 +1,232 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use super::code_type::CodeType;
+
+pub(super) struct BooleanCodeType;
+impl CodeType for BooleanCodeType {
+    fn type_label(&self) -> String {
+        "Boolean".to_string()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Byte".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("(if ({}) {{ 1 }} else {{ 0 }})", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("({} != 0)", nm)
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Boolean.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct I8CodeType;
+impl CodeType for I8CodeType {
+    fn type_label(&self) -> String {
+        "Byte".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Byte.deserializeItemFromRust({})", nm)
+    }
+}
+
+// Unsigned Rust integers are exposed as Kotlin's unsigned inline classes, but still cross
+// the JNA boundary through their signed bit-pattern equivalent (there's no unsigned JNA type).
+pub(super) struct U8CodeType;
+impl CodeType for U8CodeType {
+    fn type_label(&self) -> String {
+        "UByte".to_string()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Byte".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.toByte()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("{}.toUByte()", nm)
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("UByte.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct I16CodeType;
+impl CodeType for I16CodeType {
+    fn type_label(&self) -> String {
+        "Short".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Short.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct U16CodeType;
+impl CodeType for U16CodeType {
+    fn type_label(&self) -> String {
+        "UShort".to_string()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Short".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.toShort()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("{}.toUShort()", nm)
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("UShort.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct I32CodeType;
+impl CodeType for I32CodeType {
+    fn type_label(&self) -> String {
+        "Int".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Int.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct U32CodeType;
+impl CodeType for U32CodeType {
+    fn type_label(&self) -> String {
+        "UInt".to_string()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Int".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.toInt()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("{}.toUInt()", nm)
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("UInt.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct I64CodeType;
+impl CodeType for I64CodeType {
+    fn type_label(&self) -> String {
+        "Long".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Long.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct U64CodeType;
+impl CodeType for U64CodeType {
+    fn type_label(&self) -> String {
+        "ULong".to_string()
+    }
+    fn ffi_type_label(&self) -> String {
+        "Long".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        format!("{}.toLong()", nm)
+    }
+    fn lift(&self, nm: &str) -> String {
+        format!("{}.toULong()", nm)
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("ULong.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct FloatCodeType;
+impl CodeType for FloatCodeType {
+    fn type_label(&self) -> String {
+        "Float".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Float.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct DoubleCodeType;
+impl CodeType for DoubleCodeType {
+    fn type_label(&self) -> String {
+        "Double".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("Double.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct StringCodeType;
+impl CodeType for StringCodeType {
+    fn type_label(&self) -> String {
+        "String".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("String.deserializeItemFromRust({})", nm)
+    }
+}
+
+pub(super) struct BytesCodeType;
+impl CodeType for BytesCodeType {
+    // XXX TODO: `Bytes` was never given a proper Kotlin-facing representation; it leaks the
+    // raw JNA struct into the public API. Preserved as-is rather than fixed under the radar.
+    fn type_label(&self) -> String {
+        "RustBuffer.ByValue".to_string()
+    }
+    fn lower(&self, nm: &str) -> String {
+        nm.to_string()
+    }
+    fn lift(&self, _nm: &str) -> String {
+        panic!("[TODO: LIFT_KT Bytes]")
+    }
+    fn read(&self, nm: &str) -> String {
+        format!("RustBuffer.ByValue.deserializeItemFromRust({})", nm)
+    }
+}